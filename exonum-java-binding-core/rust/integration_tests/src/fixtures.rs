@@ -0,0 +1,61 @@
+use java_bindings::exonum::{
+    blockchain::{ExecutionResult, Service, Transaction, TransactionContext},
+    crypto::Hash,
+    messages::RawTransaction,
+    storage::Snapshot,
+};
+
+/// A service with no state and no transactions, for tests that only exercise the node
+/// and channel plumbing around `NodeContext` and never actually execute a transaction.
+pub struct EmptyService;
+
+impl Service for EmptyService {
+    fn service_id(&self) -> u16 {
+        0
+    }
+
+    fn service_name(&self) -> &str {
+        "empty_service"
+    }
+
+    fn state_hash(&self, _: &Snapshot) -> Vec<Hash> {
+        vec![]
+    }
+
+    fn tx_from_raw(&self, _: RawTransaction) -> Result<Box<dyn Transaction>, failure::Error> {
+        unimplemented!("EmptyService is never asked to execute a transaction")
+    }
+}
+
+/// A transaction that changes nothing, for services whose only purpose is to let a
+/// block actually commit the transactions submitted to it.
+#[derive(Debug, Serialize)]
+pub struct NoopTransaction;
+
+impl Transaction for NoopTransaction {
+    fn execute(&self, _context: TransactionContext) -> ExecutionResult {
+        Ok(())
+    }
+}
+
+/// A service whose transactions are all `NoopTransaction`s, for tests that need a real
+/// `Blockchain::create_patch` to run, e.g. `JavaTestKit::create_block_with_transactions`.
+pub struct NoopService;
+
+impl Service for NoopService {
+    fn service_id(&self) -> u16 {
+        0
+    }
+
+    fn service_name(&self) -> &str {
+        "noop_service"
+    }
+
+    fn state_hash(&self, _: &Snapshot) -> Vec<Hash> {
+        vec![]
+    }
+
+    fn tx_from_raw(&self, _: RawTransaction) -> Result<Box<dyn Transaction>, failure::Error> {
+        Ok(Box::new(NoopTransaction))
+    }
+}
@@ -0,0 +1,8 @@
+extern crate failure;
+extern crate java_bindings;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+
+pub mod fixtures;
+pub mod vm;
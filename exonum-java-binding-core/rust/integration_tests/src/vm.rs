@@ -0,0 +1,21 @@
+use std::sync::Arc;
+
+use java_bindings::jni::{InitArgsBuilder, JNIVersion, JavaVM};
+
+/// Classpath entry (relative to this crate) where the fake Java classes used by the
+/// integration tests are compiled to, so tests can attach to a JVM and call into Java
+/// without a full EJB build.
+const FAKE_CLASSES_CLASSPATH: &str = "../../../fakes/target/classes";
+
+/// Creates a `JavaVM` with the fake test classes on its classpath. Every test module
+/// should create exactly one of these (typically via `lazy_static!`) and share it, since
+/// a process may only host a single JVM instance.
+pub fn create_vm_for_tests_with_fake_classes() -> Arc<JavaVM> {
+    let args = InitArgsBuilder::new()
+        .version(JNIVersion::V8)
+        .option(&format!("-Djava.class.path={}", FAKE_CLASSES_CLASSPATH))
+        .build()
+        .expect("Unable to build JVM init args");
+    let vm = JavaVM::new(args).expect("Unable to create a JVM for tests");
+    Arc::new(vm)
+}
@@ -0,0 +1,54 @@
+extern crate integration_tests;
+extern crate java_bindings;
+#[macro_use]
+extern crate lazy_static;
+
+use std::sync::Arc;
+
+use integration_tests::{fixtures::NoopService, vm::create_vm_for_tests_with_fake_classes};
+use java_bindings::{
+    exonum::{
+        crypto::gen_keypair,
+        messages::{RawTransaction, ServiceTransaction},
+    },
+    jni::JavaVM,
+    JavaTestKit, MainExecutor,
+};
+
+lazy_static! {
+    static ref VM: Arc<JavaVM> = create_vm_for_tests_with_fake_classes();
+    pub static ref EXECUTOR: MainExecutor = MainExecutor::new(VM.clone());
+}
+
+#[test]
+fn create_block_advances_height_with_no_transactions() {
+    let mut testkit = create_testkit();
+    assert_eq!(testkit.height(), 0);
+
+    testkit.create_block();
+
+    assert_eq!(testkit.height(), 1);
+}
+
+#[test]
+fn create_block_with_transactions_commits_submitted_batch() {
+    let mut testkit = create_testkit();
+    let service_transaction = ServiceTransaction::from_raw_unchecked(0, vec![1, 2, 3]);
+    let raw_transaction = RawTransaction::new(0, service_transaction);
+
+    testkit.create_block_with_transactions(vec![raw_transaction]);
+
+    // The block is available synchronously right after the call returns, with no
+    // networking or consensus involved.
+    assert_eq!(testkit.height(), 1);
+    let _snapshot = testkit.snapshot();
+}
+
+fn create_testkit() -> JavaTestKit {
+    let (public_key, secret_key) = gen_keypair();
+
+    JavaTestKit::builder(EXECUTOR.clone())
+        .with_service(Box::new(NoopService))
+        .with_keypair(public_key, secret_key)
+        .build()
+}
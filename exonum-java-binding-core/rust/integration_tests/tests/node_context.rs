@@ -3,25 +3,30 @@ extern crate integration_tests;
 extern crate java_bindings;
 #[macro_use]
 extern crate lazy_static;
-extern crate failure;
 
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    iter,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
 
 use futures::{
     sync::mpsc::{self, Receiver},
     Stream,
 };
-use integration_tests::vm::create_vm_for_tests_with_fake_classes;
+use integration_tests::{fixtures::EmptyService, vm::create_vm_for_tests_with_fake_classes};
 use java_bindings::{
     exonum::{
-        blockchain::{Blockchain, Service, Transaction},
-        crypto::{gen_keypair, Hash, PublicKey, SecretKey},
-        messages::{RawTransaction, ServiceTransaction},
-        node::{ApiSender, ExternalMessage},
+        crypto::{gen_keypair, PublicKey, SecretKey},
+        helpers::{Height, ValidatorId},
+        messages::{Message, RawTransaction, ServiceTransaction},
+        node::{ApiSender, ConnectInfo, ExternalMessage},
         storage::{MemoryDB, Snapshot},
     },
     jni::JavaVM,
-    MainExecutor, NodeContext,
+    AfterCommitHandler, AllowAllTransactionFilter, MainExecutor, NodeContext, SubmitError,
+    WhitelistTransactionFilter,
 };
 
 lazy_static! {
@@ -49,6 +54,206 @@ fn submit_transaction() {
     }
 }
 
+#[test]
+fn submit_signed_relays_external_author() {
+    let keypair = gen_keypair();
+    let (node, app_rx) = create_node();
+    let service_id = 0;
+    let service_transaction = ServiceTransaction::from_raw_unchecked(0, vec![1, 2, 3]);
+    let raw_transaction = RawTransaction::new(service_id, service_transaction);
+    let message_bytes = sign_raw_transaction(raw_transaction.clone(), keypair.0, &keypair.1);
+
+    node.submit_signed(&message_bytes).unwrap();
+
+    let sent_message = app_rx.wait().next().unwrap().unwrap();
+    match sent_message {
+        ExternalMessage::Transaction(sent) => {
+            let tx_payload = sent.payload();
+            let tx_author = sent.author();
+            assert_eq!(&raw_transaction, tx_payload);
+            // The author is the external signer, not the node's own service key.
+            assert_eq!(tx_author, keypair.0);
+        }
+        _ => panic!("Message is not Transaction"),
+    }
+}
+
+#[test]
+fn submit_signed_rejects_bad_signature() {
+    let keypair = gen_keypair();
+    let (node, _app_rx) = create_node();
+    let service_transaction = ServiceTransaction::from_raw_unchecked(0, vec![1, 2, 3]);
+    let raw_transaction = RawTransaction::new(0, service_transaction);
+    let mut message_bytes = sign_raw_transaction(raw_transaction, keypair.0, &keypair.1);
+    // Flip a byte in the signature so verification must fail.
+    let last = message_bytes.len() - 1;
+    message_bytes[last] ^= 0xFF;
+
+    let err = node.submit_signed(&message_bytes).unwrap_err();
+    match err {
+        SubmitError::InvalidSignature => {}
+        other => panic!("Expected InvalidSignature, got {:?}", other),
+    }
+}
+
+#[test]
+fn submit_signed_rejects_malformed_payload() {
+    let (node, _app_rx) = create_node();
+    let err = node.submit_signed(&[0, 1, 2]).unwrap_err();
+    match err {
+        SubmitError::MalformedMessage => {}
+        other => panic!("Expected MalformedMessage, got {:?}", other),
+    }
+}
+
+/// Builds the exact wire representation `NodeContext::submit_signed` expects: a serialized
+/// Exonum `SignedMessage` carrying a `Signed<RawTransaction>`, produced the same way the
+/// framework itself signs transactions.
+fn sign_raw_transaction(
+    raw_transaction: RawTransaction,
+    public_key: PublicKey,
+    secret_key: &SecretKey,
+) -> Vec<u8> {
+    let signed = Message::concrete(raw_transaction, public_key, secret_key);
+    signed.signed_message().raw().to_vec()
+}
+
+#[test]
+fn after_commit_invokes_registered_handler() {
+    let (node, _app_rx) = create_node();
+    let observed_heights = Arc::new(Mutex::new(Vec::new()));
+    node.set_after_commit_handler(RecordingAfterCommitHandler {
+        observed_heights: observed_heights.clone(),
+    });
+
+    commit_empty_block(&node, 1);
+    commit_empty_block(&node, 2);
+
+    assert_eq!(*observed_heights.lock().unwrap(), vec![1, 2]);
+}
+
+/// Test double for the handler the JVM would normally register through JNI: it just
+/// records the heights it was invoked with instead of attaching to the JVM and
+/// calling back into Java.
+struct RecordingAfterCommitHandler {
+    observed_heights: Arc<Mutex<Vec<u64>>>,
+}
+
+impl AfterCommitHandler for RecordingAfterCommitHandler {
+    fn handle_commit(&self, height: u64, _snapshot: &Snapshot) {
+        self.observed_heights.lock().unwrap().push(height);
+    }
+}
+
+/// Drives a real `Blockchain::commit` for an empty block at `height`, without running
+/// consensus, so that any `Service::after_commit` registered on the node's blockchain
+/// (including `NodeContext`'s internal after-commit notifier) fires exactly as it would on
+/// a running node.
+fn commit_empty_block(node: &NodeContext, height: u64) {
+    let mut blockchain = node.blockchain().clone();
+    let (block_hash, patch) = blockchain.create_patch(ValidatorId(0), Height(height), &[]);
+    blockchain
+        .commit(&patch, block_hash, iter::empty())
+        .expect("Unable to commit block");
+}
+
+#[test]
+fn shutdown_sends_shutdown_message() {
+    let (node, app_rx) = create_node();
+    node.shutdown().unwrap();
+    let sent_message = app_rx.wait().next().unwrap().unwrap();
+    match sent_message {
+        ExternalMessage::Shutdown => {}
+        _ => panic!("Message is not Shutdown"),
+    }
+}
+
+#[test]
+fn rebroadcast_sends_rebroadcast_message() {
+    let (node, app_rx) = create_node();
+    node.rebroadcast().unwrap();
+    let sent_message = app_rx.wait().next().unwrap().unwrap();
+    match sent_message {
+        ExternalMessage::Rebroadcast => {}
+        _ => panic!("Message is not Rebroadcast"),
+    }
+}
+
+#[test]
+fn connect_to_peer_sends_peer_add_message() {
+    let (node, app_rx) = create_node();
+    let peer_address: SocketAddr = "127.0.0.1:7000".parse().unwrap();
+    let (peer_public_key, _) = gen_keypair();
+    node.connect_to_peer(peer_address, peer_public_key).unwrap();
+    let sent_message = app_rx.wait().next().unwrap().unwrap();
+    match sent_message {
+        ExternalMessage::PeerAdd(connect_info) => assert_eq!(
+            connect_info,
+            ConnectInfo {
+                address: peer_address.to_string(),
+                public_key: peer_public_key,
+            }
+        ),
+        _ => panic!("Message is not PeerAdd"),
+    }
+}
+
+#[test]
+fn submit_allows_any_author_with_allow_all_filter() {
+    let keypair = gen_keypair();
+    let (node, app_rx) = create_node_with_keypair(keypair.0, keypair.1);
+    node.set_transaction_filter(Arc::new(AllowAllTransactionFilter));
+
+    let service_transaction = ServiceTransaction::from_raw_unchecked(0, vec![1, 2, 3]);
+    let raw_transaction = RawTransaction::new(0, service_transaction);
+    node.submit(raw_transaction).unwrap();
+
+    let sent_message = app_rx.wait().next().unwrap().unwrap();
+    match sent_message {
+        ExternalMessage::Transaction(_) => {}
+        _ => panic!("Message is not Transaction"),
+    }
+}
+
+#[test]
+fn submit_allows_whitelisted_author() {
+    let keypair = gen_keypair();
+    let (node, app_rx) = create_node_with_keypair(keypair.0, keypair.1);
+    let whitelist: HashSet<PublicKey> = vec![keypair.0].into_iter().collect();
+    node.set_transaction_filter(Arc::new(WhitelistTransactionFilter::new(whitelist)));
+
+    let service_transaction = ServiceTransaction::from_raw_unchecked(0, vec![1, 2, 3]);
+    let raw_transaction = RawTransaction::new(0, service_transaction);
+    node.submit(raw_transaction).unwrap();
+
+    let sent_message = app_rx.wait().next().unwrap().unwrap();
+    match sent_message {
+        ExternalMessage::Transaction(_) => {}
+        _ => panic!("Message is not Transaction"),
+    }
+}
+
+#[test]
+fn submit_rejects_non_whitelisted_author() {
+    let keypair = gen_keypair();
+    let other_keypair = gen_keypair();
+    let (node, app_rx) = create_node_with_keypair(keypair.0, keypair.1);
+    let whitelist: HashSet<PublicKey> = vec![other_keypair.0].into_iter().collect();
+    node.set_transaction_filter(Arc::new(WhitelistTransactionFilter::new(whitelist)));
+
+    let service_transaction = ServiceTransaction::from_raw_unchecked(0, vec![1, 2, 3]);
+    let raw_transaction = RawTransaction::new(0, service_transaction);
+    let err = node.submit(raw_transaction).unwrap_err();
+    match err {
+        SubmitError::Rejected => {}
+        other => panic!("Expected Rejected, got {:?}", other),
+    }
+
+    // The rejected transaction must never reach the channel.
+    drop(node);
+    assert!(app_rx.wait().next().is_none());
+}
+
 fn create_node() -> (NodeContext, Receiver<ExternalMessage>) {
     let service_keypair = gen_keypair();
     create_node_with_keypair(service_keypair.0, service_keypair.1)
@@ -61,34 +266,14 @@ fn create_node_with_keypair(
     let api_channel = mpsc::channel(128);
     let (app_tx, app_rx) = (ApiSender::new(api_channel.0), api_channel.1);
 
-    struct EmptyService;
-
-    impl Service for EmptyService {
-        fn service_id(&self) -> u16 {
-            0
-        }
-
-        fn service_name(&self) -> &str {
-            "empty_service"
-        }
-
-        fn state_hash(&self, _: &Snapshot) -> Vec<Hash> {
-            vec![]
-        }
-
-        fn tx_from_raw(&self, _: RawTransaction) -> Result<Box<dyn Transaction>, failure::Error> {
-            unimplemented!()
-        }
-    }
-
     let storage = MemoryDB::new();
-    let blockchain = Blockchain::new(
+    let node = NodeContext::new(
+        EXECUTOR.clone(),
         storage,
         vec![Box::new(EmptyService)],
         public_key,
         secret_key,
-        app_tx.clone(),
+        app_tx,
     );
-    let node = NodeContext::new(EXECUTOR.clone(), blockchain, public_key, app_tx);
     (node, app_rx)
 }
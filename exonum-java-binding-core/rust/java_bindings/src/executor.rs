@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use jni::JavaVM;
+
+/// Runs closures on the shared JVM, attaching the calling thread first.
+///
+/// Cloning a `MainExecutor` is cheap: it only clones the underlying `Arc<JavaVM>`, so every
+/// clone drives the same JVM instance.
+#[derive(Clone)]
+pub struct MainExecutor {
+    vm: Arc<JavaVM>,
+}
+
+impl MainExecutor {
+    pub fn new(vm: Arc<JavaVM>) -> Self {
+        MainExecutor { vm }
+    }
+
+    /// Attaches the current thread to the JVM (if it is not attached already) and runs `f`
+    /// with the resulting `JNIEnv`.
+    pub fn with_attached<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&jni::JNIEnv) -> R,
+    {
+        let env = self
+            .vm
+            .attach_current_thread()
+            .expect("Unable to attach the current thread to the JVM");
+        f(&env)
+    }
+}
@@ -0,0 +1,19 @@
+// Re-exported so that downstream crates (e.g. `integration_tests`) can refer to the exact
+// `exonum` and `jni` versions this crate was built against without declaring their own
+// dependency on them.
+pub extern crate exonum;
+pub extern crate jni;
+
+extern crate failure;
+extern crate futures;
+
+mod executor;
+mod node;
+mod testkit;
+
+pub use executor::MainExecutor;
+pub use node::{
+    AfterCommitHandler, AllowAllTransactionFilter, NodeContext, SubmitError, TransactionFilter,
+    WhitelistTransactionFilter,
+};
+pub use testkit::{JavaTestKit, JavaTestKitBuilder};
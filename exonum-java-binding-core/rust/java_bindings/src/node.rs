@@ -0,0 +1,281 @@
+use std::{
+    collections::HashSet,
+    fmt,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use exonum::{
+    blockchain::{Blockchain, Service, ServiceContext, Transaction},
+    crypto::{Hash, PublicKey, SecretKey},
+    messages::{
+        Message, ProtocolMessage, RawTransaction, Signed, SignedMessage, EMPTY_SIGNED_MESSAGE_SIZE,
+    },
+    node::{ApiSender, ConnectInfo, ExternalMessage},
+    storage::{Database, Snapshot},
+};
+
+use executor::MainExecutor;
+
+/// Errors that can occur while submitting a transaction or control message to the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitError {
+    /// `submit_signed` was given bytes that are not a well-formed `SignedMessage`.
+    MalformedMessage,
+    /// The Ed25519 signature embedded in the message does not match the claimed author.
+    InvalidSignature,
+    /// The node's `ApiSender` channel is closed, e.g. because the node is shutting down.
+    NodeUnavailable,
+    /// The node's `TransactionFilter` refused the transaction.
+    Rejected,
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            SubmitError::MalformedMessage => "transaction message is malformed",
+            SubmitError::InvalidSignature => {
+                "transaction signature does not match its claimed author"
+            }
+            SubmitError::NodeUnavailable => "the node is not accepting submissions",
+            SubmitError::Rejected => "the transaction was refused by the node's filter",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Decides which transactions the node accepts before it broadcasts them (and, in `submit`,
+/// before it signs them), e.g. to restrict submission to a known set of authors.
+pub trait TransactionFilter: Send + Sync {
+    fn allow(&self, author: PublicKey, service_id: u16, raw_transaction: &RawTransaction) -> bool;
+}
+
+/// Accepts every transaction, regardless of its author. This is the default filter.
+pub struct AllowAllTransactionFilter;
+
+impl TransactionFilter for AllowAllTransactionFilter {
+    fn allow(
+        &self,
+        _author: PublicKey,
+        _service_id: u16,
+        _raw_transaction: &RawTransaction,
+    ) -> bool {
+        true
+    }
+}
+
+/// Accepts transactions only from a fixed set of authors.
+pub struct WhitelistTransactionFilter {
+    allowed_authors: HashSet<PublicKey>,
+}
+
+impl WhitelistTransactionFilter {
+    pub fn new(allowed_authors: HashSet<PublicKey>) -> Self {
+        WhitelistTransactionFilter { allowed_authors }
+    }
+}
+
+impl TransactionFilter for WhitelistTransactionFilter {
+    fn allow(
+        &self,
+        author: PublicKey,
+        _service_id: u16,
+        _raw_transaction: &RawTransaction,
+    ) -> bool {
+        self.allowed_authors.contains(&author)
+    }
+}
+
+/// Receives a notification every time a block is committed to the blockchain.
+///
+/// Implementations are invoked on the thread that commits the block, after `MainExecutor`
+/// has attached that thread to the JVM, so they are free to call back into Java.
+pub trait AfterCommitHandler: Send + Sync {
+    fn handle_commit(&self, height: u64, snapshot: &Snapshot);
+}
+
+type AfterCommitHandlerRegistry = Arc<Mutex<Option<Box<dyn AfterCommitHandler>>>>;
+
+/// The `service_id` reserved for `AfterCommitNotifier`. Application services passed to
+/// `NodeContext::new` must not use it.
+const AFTER_COMMIT_SERVICE_ID: u16 = u16::max_value();
+
+/// A `Service` with no state and no transactions of its own, registered automatically by
+/// `NodeContext::new` purely so that `Blockchain::commit` actually calls `after_commit` on
+/// it, letting it forward the notification to whatever `AfterCommitHandler` is currently
+/// registered on the shared `NodeContext`.
+struct AfterCommitNotifier {
+    executor: MainExecutor,
+    handler: AfterCommitHandlerRegistry,
+}
+
+impl Service for AfterCommitNotifier {
+    fn service_id(&self) -> u16 {
+        AFTER_COMMIT_SERVICE_ID
+    }
+
+    fn service_name(&self) -> &str {
+        "java_bindings_after_commit"
+    }
+
+    fn state_hash(&self, _snapshot: &Snapshot) -> Vec<Hash> {
+        vec![]
+    }
+
+    fn tx_from_raw(&self, _raw: RawTransaction) -> Result<Box<dyn Transaction>, failure::Error> {
+        Err(failure::err_msg(
+            "the internal after-commit service accepts no transactions",
+        ))
+    }
+
+    fn after_commit(&self, context: &ServiceContext) {
+        self.executor.with_attached(|_env| {
+            if let Some(handler) = self.handler.lock().unwrap().as_ref() {
+                handler.handle_commit(context.height().0, context.snapshot());
+            }
+        });
+    }
+}
+
+/// The facade Java services use to interact with the running node: submitting
+/// transactions, either their own or relayed from external clients, and observing commits.
+pub struct NodeContext {
+    executor: MainExecutor,
+    blockchain: Blockchain,
+    public_key: PublicKey,
+    secret_key: SecretKey,
+    api_sender: ApiSender,
+    after_commit_handler: AfterCommitHandlerRegistry,
+    transaction_filter: Mutex<Arc<dyn TransactionFilter>>,
+}
+
+impl NodeContext {
+    /// Builds a `NodeContext` around a fresh `Blockchain` over `storage` and `services`,
+    /// plus an internal service that lets `set_after_commit_handler` actually fire from real
+    /// block commits.
+    pub fn new<D: Into<Arc<dyn Database>>>(
+        executor: MainExecutor,
+        storage: D,
+        mut services: Vec<Box<dyn Service>>,
+        public_key: PublicKey,
+        secret_key: SecretKey,
+        api_sender: ApiSender,
+    ) -> Self {
+        let after_commit_handler = Arc::new(Mutex::new(None));
+        services.push(Box::new(AfterCommitNotifier {
+            executor: executor.clone(),
+            handler: after_commit_handler.clone(),
+        }));
+        let blockchain = Blockchain::new(
+            storage,
+            services,
+            public_key,
+            secret_key.clone(),
+            api_sender.clone(),
+        );
+        NodeContext {
+            executor,
+            blockchain,
+            public_key,
+            secret_key,
+            api_sender,
+            after_commit_handler,
+            transaction_filter: Mutex::new(Arc::new(AllowAllTransactionFilter)),
+        }
+    }
+
+    pub fn blockchain(&self) -> &Blockchain {
+        &self.blockchain
+    }
+
+    /// Replaces the filter used to decide which transactions `submit` and
+    /// `submit_signed` accept. Defaults to `AllowAllTransactionFilter`.
+    pub fn set_transaction_filter(&self, filter: Arc<dyn TransactionFilter>) {
+        *self.transaction_filter.lock().unwrap() = filter;
+    }
+
+    /// Registers the handler invoked after every block commit, replacing any handler that
+    /// was previously set.
+    pub fn set_after_commit_handler<H: AfterCommitHandler + 'static>(&self, handler: H) {
+        *self.after_commit_handler.lock().unwrap() = Some(Box::new(handler));
+    }
+
+    /// Signs `raw_transaction` with the node's own service keypair and broadcasts it.
+    pub fn submit(&self, raw_transaction: RawTransaction) -> Result<(), SubmitError> {
+        self.check_filter(self.public_key, &raw_transaction)?;
+        let signed = Message::concrete(raw_transaction, self.public_key, &self.secret_key);
+        self.send_external_message(ExternalMessage::Transaction(signed))
+    }
+
+    /// Relays a transaction that was already signed by an external client (e.g. a light
+    /// wallet), preserving its author instead of re-signing with the node's own keypair.
+    ///
+    /// `message_bytes` must be a well-formed Exonum `SignedMessage` wire buffer (the same
+    /// format produced by `Signed::serialize`/`SignedMessage::raw`), encoding a
+    /// `Signed<RawTransaction>`.
+    pub fn submit_signed(&self, message_bytes: &[u8]) -> Result<(), SubmitError> {
+        let signed = decode_signed_transaction(message_bytes)?;
+        self.check_filter(signed.author(), signed.payload())?;
+        self.send_external_message(ExternalMessage::Transaction(signed))
+    }
+
+    /// Requests that the node shut down.
+    pub fn shutdown(&self) -> Result<(), SubmitError> {
+        self.send_external_message(ExternalMessage::Shutdown)
+    }
+
+    /// Requests that the node rebroadcast its unconfirmed transactions to its peers.
+    pub fn rebroadcast(&self) -> Result<(), SubmitError> {
+        self.send_external_message(ExternalMessage::Rebroadcast)
+    }
+
+    /// Requests that the node open a connection to the peer listening at `address`,
+    /// identified by `public_key`.
+    pub fn connect_to_peer(
+        &self,
+        address: SocketAddr,
+        public_key: PublicKey,
+    ) -> Result<(), SubmitError> {
+        self.send_external_message(ExternalMessage::PeerAdd(ConnectInfo {
+            address: address.to_string(),
+            public_key,
+        }))
+    }
+
+    fn check_filter(
+        &self,
+        author: PublicKey,
+        raw_transaction: &RawTransaction,
+    ) -> Result<(), SubmitError> {
+        let filter = self.transaction_filter.lock().unwrap();
+        let service_id = raw_transaction.service_id();
+        if filter.allow(author, service_id, raw_transaction) {
+            Ok(())
+        } else {
+            Err(SubmitError::Rejected)
+        }
+    }
+
+    fn send_external_message(&self, message: ExternalMessage) -> Result<(), SubmitError> {
+        self.api_sender
+            .send_external_message(message)
+            .map_err(|_| SubmitError::NodeUnavailable)
+    }
+}
+
+fn decode_signed_transaction(message_bytes: &[u8]) -> Result<Signed<RawTransaction>, SubmitError> {
+    if message_bytes.len() <= EMPTY_SIGNED_MESSAGE_SIZE {
+        return Err(SubmitError::MalformedMessage);
+    }
+
+    // `SignedMessage::from_raw_buffer` checks the binary format and verifies the Ed25519
+    // signature; since the length check above already passed, any failure here must be a
+    // signature mismatch.
+    let signed_message = SignedMessage::from_raw_buffer(message_bytes.to_vec())
+        .map_err(|_| SubmitError::InvalidSignature)?;
+    let message =
+        Message::deserialize(signed_message).map_err(|_| SubmitError::MalformedMessage)?;
+    RawTransaction::try_from(message).map_err(|_| SubmitError::MalformedMessage)
+}
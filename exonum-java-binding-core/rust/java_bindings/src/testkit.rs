@@ -0,0 +1,139 @@
+use std::{
+    iter,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use exonum::{
+    blockchain::{Blockchain, GenesisConfig, Schema, Service, ValidatorKeys},
+    crypto::{gen_keypair, PublicKey, SecretKey},
+    helpers::{Height, ValidatorId},
+    messages::{Message, RawTransaction},
+    node::ApiSender,
+    storage::{MemoryDB, Snapshot},
+};
+use futures::sync::mpsc;
+
+use executor::MainExecutor;
+
+/// Builds a [`JavaTestKit`] from the services under test and, optionally, a fixed
+/// service keypair (a fresh one is generated otherwise).
+pub struct JavaTestKitBuilder {
+    executor: MainExecutor,
+    services: Vec<Box<dyn Service>>,
+    keypair: Option<(PublicKey, SecretKey)>,
+}
+
+impl JavaTestKitBuilder {
+    fn new(executor: MainExecutor) -> Self {
+        JavaTestKitBuilder {
+            executor,
+            services: Vec::new(),
+            keypair: None,
+        }
+    }
+
+    pub fn with_service(mut self, service: Box<dyn Service>) -> Self {
+        self.services.push(service);
+        self
+    }
+
+    pub fn with_keypair(mut self, public_key: PublicKey, secret_key: SecretKey) -> Self {
+        self.keypair = Some((public_key, secret_key));
+        self
+    }
+
+    pub fn build(self) -> JavaTestKit {
+        let (public_key, secret_key) = self.keypair.unwrap_or_else(gen_keypair);
+        // The testkit never actually sends external messages: transactions are committed
+        // synchronously by `create_block_with_transactions` instead of going through the
+        // node's mempool, so nothing ever reads from this channel.
+        let (api_tx, _api_rx) = mpsc::channel(128);
+        let api_sender = ApiSender::new(api_tx);
+        let storage = MemoryDB::new();
+        let mut blockchain = Blockchain::new(
+            storage,
+            self.services,
+            public_key,
+            secret_key.clone(),
+            api_sender,
+        );
+        let genesis_config = GenesisConfig::new(iter::once(ValidatorKeys {
+            consensus_key: public_key,
+            service_key: public_key,
+        }));
+        blockchain
+            .initialize(genesis_config)
+            .expect("Unable to create the genesis block");
+
+        JavaTestKit {
+            executor: self.executor,
+            blockchain,
+            public_key,
+            secret_key,
+            height: AtomicU64::new(0),
+        }
+    }
+}
+
+/// An in-process harness for testing Java service logic: it builds a `Blockchain` over
+/// `MemoryDB` and lets the caller advance it block-by-block in the same thread, without
+/// networking or consensus.
+pub struct JavaTestKit {
+    executor: MainExecutor,
+    blockchain: Blockchain,
+    public_key: PublicKey,
+    secret_key: SecretKey,
+    height: AtomicU64,
+}
+
+impl JavaTestKit {
+    pub fn builder(executor: MainExecutor) -> JavaTestKitBuilder {
+        JavaTestKitBuilder::new(executor)
+    }
+
+    pub fn height(&self) -> u64 {
+        self.height.load(Ordering::SeqCst)
+    }
+
+    pub fn snapshot(&self) -> Box<dyn Snapshot> {
+        self.blockchain.snapshot()
+    }
+
+    /// Commits an empty block.
+    pub fn create_block(&mut self) {
+        self.create_block_with_transactions(Vec::new());
+    }
+
+    /// Commits a block containing `raw_transactions`, synchronously and in the calling
+    /// thread, then advances `height()` by one.
+    pub fn create_block_with_transactions(&mut self, raw_transactions: Vec<RawTransaction>) {
+        self.executor.with_attached(|_env| {
+            let mut fork = self.blockchain.fork();
+            let tx_hashes: Vec<_> = {
+                let mut schema = Schema::new(&mut fork);
+                raw_transactions
+                    .into_iter()
+                    .map(|raw_transaction| {
+                        let signed =
+                            Message::concrete(raw_transaction, self.public_key, &self.secret_key);
+                        let tx_hash = signed.hash();
+                        schema.add_transaction_into_pool(signed);
+                        tx_hash
+                    })
+                    .collect()
+            };
+            self.blockchain
+                .merge(fork.into_patch())
+                .expect("Unable to add transactions into the pool");
+
+            let next_height = Height(self.height() + 1);
+            let (block_hash, patch) =
+                self.blockchain
+                    .create_patch(ValidatorId(0), next_height, &tx_hashes);
+            self.blockchain
+                .commit(&patch, block_hash, iter::empty())
+                .expect("Unable to commit a block in JavaTestKit");
+            self.height.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+}